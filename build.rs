@@ -39,8 +39,29 @@ macro_rules! cargo_env {
 /// pointer width.
 const CARGO_CFG_TARGET_POINTER_WIDTH: &'static str = "CARGO_CFG_TARGET_POINTER_WIDTH";
 
+/// Name of the environment variable provided by Cargo to specify the target
+/// operating system.
+///
+/// Build scripts are always compiled and run for the *host*, so the target
+/// must be read from this Cargo-provided variable rather than inferred from
+/// `cfg!`/`#[cfg(..)]`, which would instead reflect the host running
+/// `build.rs` and get cross-compilation wrong.
+const CARGO_CFG_TARGET_OS: &'static str = "CARGO_CFG_TARGET_OS";
+
+/// Name of the environment variable reporting which DEFLATE/crypto backend
+/// was selected at build time.
+const COMPRESSION_BACKEND: &'static str = "COMPRESSION_BACKEND";
+/// Minimum system `zlib` version accepted when the `system-zlib` feature is
+/// enabled.
+#[cfg(feature = "system-zlib")]
+const ZLIB_MIN_VERSION: &'static str = "1.2.8";
+
 /// The default prefix used in constructing the path to the different
-/// installation directories.
+/// installation directories when targeting a Unix-like system.
+///
+/// There is no `/usr/local`-style convention on Windows, so the default
+/// prefix is left empty there (see [`default_prefix`]) and every directory
+/// variable falls back to a path relative to the current drive root.
 const DEFAULT_PREFIX: &'static str = "/usr/local";
 
 /// Name of the environment variable to a prefix used in constructing the
@@ -208,6 +229,85 @@ impl fmt::Display for EnvVar {
     }
 }
 
+impl EnvVar {
+    /// Normalize the path held by this environment variable so it is usable
+    /// by both Rust [`Path`](std::path::Path) consumers and shell-based
+    /// install tooling, regardless of host platform. See [`sanitize_path`].
+    fn sanitized(mut self) -> Self {
+        self.value = self.value.as_deref().map(sanitize_path);
+        self
+    }
+}
+
+/// Normalize a path so it is portable across Unix and Windows path
+/// conventions.
+///
+/// This mirrors the technique used by rustc's installer: backslashes are
+/// turned into forward slashes, a leading `//?/` UNC prefix is stripped, and
+/// a `C:/…` drive-letter path is rewritten into `/C/…`.
+fn sanitize_path(path: &str) -> String {
+    let path = path.replace('\\', "/");
+    let path = path.strip_prefix("//?/").unwrap_or(&path);
+
+    let bytes = path.as_bytes();
+    if bytes.len() > 2 && bytes[1] == b':' && bytes[2] == b'/' {
+        format!("/{}{}", &path[..1], &path[2..])
+    } else {
+        path.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn leaves_unix_path_unchanged() {
+        assert_eq!(sanitize_path("/usr/local/bin"), "/usr/local/bin");
+    }
+
+    #[test]
+    fn rewrites_drive_letter_path() {
+        assert_eq!(sanitize_path("C:\\Users\\x"), "/C/Users/x");
+    }
+
+    #[test]
+    fn strips_unc_prefix_and_rewrites_drive_letter() {
+        assert_eq!(sanitize_path("//?/C:/Users/x"), "/C/Users/x");
+    }
+
+    #[test]
+    fn leaves_colon_without_following_slash_unchanged() {
+        assert_eq!(sanitize_path("a:b"), "a:b");
+    }
+}
+
+/// Whether the crate is being built for a Windows target.
+///
+/// Reads the target from the Cargo-provided `CARGO_CFG_TARGET_OS`
+/// environment variable rather than `cfg!(windows)`/`#[cfg(windows)]`, which
+/// would reflect the *host* `build.rs` runs on and get cross-compilation
+/// wrong.
+fn is_windows_target() -> bool {
+    env::var(CARGO_CFG_TARGET_OS)
+        .map(|os| os == "windows")
+        .unwrap_or(false)
+}
+
+/// The default prefix used in constructing the path to the different
+/// installation directories, resolved for the target platform.
+///
+/// There is no `/usr/local`-style convention on Windows, so the default
+/// prefix is left empty there and every directory variable falls back to a
+/// path relative to the current drive root.
+fn default_prefix() -> String {
+    if is_windows_target() {
+        String::new()
+    } else {
+        String::from(DEFAULT_PREFIX)
+    }
+}
+
 /// Installation directories should always be named by variables, so it is easy
 /// to install in a nonstandard place. This function emit installation
 /// directories environment variables so the built code can use them to define
@@ -224,7 +324,7 @@ impl fmt::Display for EnvVar {
 /// provided.
 fn install_dirs() {
     let mut dir: HashMap<&str, EnvVar> = HashMap::new();
-    dir.insert(PREFIX, EnvVar::get(PREFIX).or(DEFAULT_PREFIX));
+    dir.insert(PREFIX, EnvVar::get(PREFIX).or(&default_prefix()));
     dir.insert(
         EXEC_PREFIX,
         EnvVar::get(EXEC_PREFIX).or_from(&dir[PREFIX])
@@ -232,18 +332,23 @@ fn install_dirs() {
 
     // The `/lib` folder will vary based on the pointer size. For example,
     // libraries targeting a 64-bit CPU will be installed under `/lib64`.
-    let qual = match EnvVar::get(CARGO_CFG_TARGET_POINTER_WIDTH).value.unwrap() {
-        x => if x.parse::<i32>().unwrap() >= 64 { x } else { String::from("") },
+    // There is no such qualifier on Windows, which has no `lib`/`lib64` split.
+    let qual = if is_windows_target() {
+        String::new()
+    } else {
+        match EnvVar::get(CARGO_CFG_TARGET_POINTER_WIDTH).value.unwrap() {
+            x => if x.parse::<i32>().unwrap() >= 64 { x } else { String::from("") },
+        }
     };
 
     // Set prefix variables.
-    cargo_env!(dir[PREFIX]);
-    cargo_env!(dir[EXEC_PREFIX]);
+    cargo_env!(dir[PREFIX].clone().sanitized());
+    cargo_env!(dir[EXEC_PREFIX].clone().sanitized());
 
     // Define root variables.
     for (k, v) in ROOT_DIRS.iter() {
         dir.insert(k, EnvVar::get(k).or(v));
-        cargo_env!(dir[k]);
+        cargo_env!(dir[k].clone().sanitized());
     }
 
     // Define prefix dependent variables.
@@ -252,29 +357,61 @@ fn install_dirs() {
 
     for (k, v) in PREFIX_DIRS.iter() {
         dir.insert(k, EnvVar::get(k).or(&format!("{}{}", prefix, v)));
-        cargo_env!(dir[k]);
+        cargo_env!(dir[k].clone().sanitized());
     }
 
     for (k, v) in EXEC_DIRS.iter() {
-        cargo_env!(EnvVar::get(k).or(&format!("{}{}", exec_prefix, v)));
+        cargo_env!(EnvVar::get(k).or(&format!("{}{}", exec_prefix, v)).sanitized());
     }
 
     for (k, v) in LIB_DIRS.iter() {
-        cargo_env!(EnvVar::get(k).or(&format!("{}{}{}", exec_prefix, v, qual)));
+        cargo_env!(EnvVar::get(k).or(&format!("{}{}{}", exec_prefix, v, qual)).sanitized());
     }
 
     // `DATADIR` equals to `DATAROOTDIR` if not set already.
     dir.insert(DATADIR, EnvVar::get(DATADIR).or_from(&dir[DATAROOTDIR]));
-    cargo_env!(dir[DATADIR]);
+    cargo_env!(dir[DATADIR].clone().sanitized());
 
     // Define `DATAROOTDIR` dependent variables.
     let datarootdir = dir[DATAROOTDIR].value.clone().unwrap();
     for (k, v) in DATA_DIRS.iter() {
         dir.insert(k, EnvVar::get(k).or(&format!("{}{}", datarootdir, v)));
-        cargo_env!(dir[k]);
+        cargo_env!(dir[k].clone().sanitized());
     }
 }
 
+/// Probe for a system `zlib` installation when the `system-zlib` feature is
+/// enabled, selecting `bundled` when the feature is off or probing fails.
+/// On success the probe emits the link lines for the system library. Only
+/// the selection is made here: the crate contains no compression code and
+/// no bundled implementation that consumes it, and `openssl`/`libssh2` are
+/// not probed.
+///
+/// The chosen backend is emitted as the `COMPRESSION_BACKEND` environment
+/// variable so the built code can report which path is active at runtime.
+fn deflate_backend() {
+    #[cfg(feature = "system-zlib")]
+    let backend = match pkg_config::Config::new()
+        .atleast_version(ZLIB_MIN_VERSION)
+        .probe("zlib")
+    {
+        Ok(_) => "system-zlib",
+        Err(err) => {
+            println!(
+                "cargo:warning=system zlib not found ({}), falling back to the bundled implementation",
+                err
+            );
+            "bundled"
+        }
+    };
+
+    #[cfg(not(feature = "system-zlib"))]
+    let backend = "bundled";
+
+    cargo_env!(format!("{}={}", COMPRESSION_BACKEND, backend));
+}
+
 fn main() {
     install_dirs();
+    deflate_backend();
 }
@@ -0,0 +1,163 @@
+/* src/dirs.rs
+ * ===========
+ *
+ * Copying
+ * -------
+ *
+ * Copyright (c) 2022 gitrs authors and contributors.
+ *
+ * This file is part of the *gitrs* project.
+ *
+ * gitrs is a free software project. You can redistribute it and/or modify it
+ * following the terms of the MIT License.
+ *
+ * This software project is distributed *as is*, WITHOUT WARRANTY OF ANY KIND;
+ * including but not limited to the WARRANTIES OF MERCHANTABILITY, FITNESS FOR A
+ * PARTICULAR PURPOSE and NONINFRINGEMENT.
+ *
+ * You should have received a copy of the MIT License along with *gitrs*. If
+ * not, see <http://opensource.org/licenses/MIT>.
+ */
+
+//! Resolved installation directories.
+//!
+//! `build.rs` computes the full set of
+//! [GNU Variables for Installation Directories](https://www.gnu.org/prep/standards/html_node/Directory-Variables.html)
+//! and emits them as `rustc-env` variables so they are baked into the build.
+//! This module is the single place that reads them back through [`env!()`],
+//! so the prefix/sysconfdir/datadir/mandir/libdir logic is never re-derived
+//! downstream.
+//!
+//! Each directory is exposed as a `&'static str` constant plus a `&'static
+//! Path`-returning accessor of the same name in `snake_case`; the accessor
+//! just wraps the constant, so its own doc comment only points back to it
+//! instead of repeating the rationale.
+
+use std::path::Path;
+
+/// Directory a user-specified prefix was resolved to, or the built-in
+/// default when none was provided.
+pub const PREFIX: &'static str = env!("PREFIX");
+/// Directory a user-specified executable prefix was resolved to.
+pub const EXEC_PREFIX: &'static str = env!("EXEC_PREFIX");
+
+/// Directory for installing executable programs that users can run.
+pub const BINDIR: &'static str = env!("BINDIR");
+/// Directory for idiosyncratic read-only architecture-independent data
+/// files for this package.
+pub const DATADIR: &'static str = env!("DATADIR");
+/// Root of the directory tree for read-only architecture-independent data
+/// files.
+pub const DATAROOTDIR: &'static str = env!("DATAROOTDIR");
+/// Directory for installing documentation files (other than Info or Man)
+/// for this package.
+pub const DOCDIR: &'static str = env!("DOCDIR");
+/// Directory for installing header files to be included by user programs
+/// with the C ‘`#include`’ preprocessor directive.
+pub const INCLUDEDIR: &'static str = env!("INCLUDEDIR");
+/// Directory for installing the Info files for this package.
+pub const INFODIR: &'static str = env!("INFODIR");
+/// Directory for object files and libraries of object code.
+pub const LIBDIR: &'static str = env!("LIBDIR");
+/// Directory for installing executable programs to be run by other
+/// programs rather than by users.
+pub const LIBEXECDIR: &'static str = env!("LIBEXECDIR");
+/// Directory for installing data files which the programs modify while
+/// they run, and that pertain to one specific machine.
+pub const LOCALSTATEDIR: &'static str = env!("LOCALSTATEDIR");
+/// Top-level directory for installing the man pages (if any) for this
+/// package.
+pub const MANDIR: &'static str = env!("MANDIR");
+/// Directory for installing data files which the programs modify while
+/// they run, that pertain to one specific machine, and which need not
+/// persist longer than the execution of the program.
+pub const RUNSTATEDIR: &'static str = env!("RUNSTATEDIR");
+/// Directory for installing executable programs that can be run from the
+/// shell, but are only generally useful to system administrators.
+pub const SBINDIR: &'static str = env!("SBINDIR");
+/// Directory for installing architecture-independent data files which the
+/// programs modify while they run.
+pub const SHAREDSTATEDIR: &'static str = env!("SHAREDSTATEDIR");
+/// Directory for installing read-only data files that pertain to a single
+/// machine, that is to say, files for configuring a host.
+pub const SYSCONFDIR: &'static str = env!("SYSCONFDIR");
+
+/// See [`PREFIX`].
+pub fn prefix() -> &'static Path {
+    Path::new(PREFIX)
+}
+
+/// See [`EXEC_PREFIX`].
+pub fn exec_prefix() -> &'static Path {
+    Path::new(EXEC_PREFIX)
+}
+
+/// See [`BINDIR`].
+pub fn bindir() -> &'static Path {
+    Path::new(BINDIR)
+}
+
+/// See [`DATADIR`].
+pub fn datadir() -> &'static Path {
+    Path::new(DATADIR)
+}
+
+/// See [`DATAROOTDIR`].
+pub fn datarootdir() -> &'static Path {
+    Path::new(DATAROOTDIR)
+}
+
+/// See [`DOCDIR`].
+pub fn docdir() -> &'static Path {
+    Path::new(DOCDIR)
+}
+
+/// See [`INCLUDEDIR`].
+pub fn includedir() -> &'static Path {
+    Path::new(INCLUDEDIR)
+}
+
+/// See [`INFODIR`].
+pub fn infodir() -> &'static Path {
+    Path::new(INFODIR)
+}
+
+/// See [`LIBDIR`].
+pub fn libdir() -> &'static Path {
+    Path::new(LIBDIR)
+}
+
+/// See [`LIBEXECDIR`].
+pub fn libexecdir() -> &'static Path {
+    Path::new(LIBEXECDIR)
+}
+
+/// See [`LOCALSTATEDIR`].
+pub fn localstatedir() -> &'static Path {
+    Path::new(LOCALSTATEDIR)
+}
+
+/// See [`MANDIR`].
+pub fn mandir() -> &'static Path {
+    Path::new(MANDIR)
+}
+
+/// See [`RUNSTATEDIR`].
+pub fn runstatedir() -> &'static Path {
+    Path::new(RUNSTATEDIR)
+}
+
+/// See [`SBINDIR`].
+pub fn sbindir() -> &'static Path {
+    Path::new(SBINDIR)
+}
+
+/// See [`SHAREDSTATEDIR`].
+pub fn sharedstatedir() -> &'static Path {
+    Path::new(SHAREDSTATEDIR)
+}
+
+/// See [`SYSCONFDIR`].
+pub fn sysconfdir() -> &'static Path {
+    Path::new(SYSCONFDIR)
+}
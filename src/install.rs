@@ -0,0 +1,215 @@
+/* src/install.rs
+ * ==============
+ *
+ * Copying
+ * -------
+ *
+ * Copyright (c) 2022 gitrs authors and contributors.
+ *
+ * This file is part of the *gitrs* project.
+ *
+ * gitrs is a free software project. You can redistribute it and/or modify it
+ * following the terms of the MIT License.
+ *
+ * This software project is distributed *as is*, WITHOUT WARRANTY OF ANY KIND;
+ * including but not limited to the WARRANTIES OF MERCHANTABILITY, FITNESS FOR A
+ * PARTICULAR PURPOSE and NONINFRINGEMENT.
+ *
+ * You should have received a copy of the MIT License along with *gitrs*. If
+ * not, see <http://opensource.org/licenses/MIT>.
+ */
+
+//! Staged installation support.
+//!
+//! Lays artifacts out under the installation directories resolved by
+//! [`dirs`](crate::dirs), honoring the standard GNU `DESTDIR` convention for
+//! staged/packaged installs: every resolved directory is prefixed with
+//! `DESTDIR` before anything is written, so distro packagers can build into
+//! a fakeroot without touching the live filesystem. This mirrors rustc's
+//! installer flow: compute each destination from the resolved directories,
+//! then copy (never move) files into place.
+
+use std::fs;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+
+use crate::dirs;
+
+/// Name of the environment variable prepended to every installation
+/// directory for staged/packaged installs.
+const DESTDIR: &'static str = "DESTDIR";
+
+/// A single file to be staged into one of the resolved installation
+/// directories.
+#[derive(Clone, Debug)]
+pub struct Artifact {
+    /// Path to the file as built, relative to the current working
+    /// directory or absolute.
+    pub src: PathBuf,
+    /// Installation directory the file belongs under.
+    pub dir: PathBuf,
+    /// File name to give the artifact once installed. Must be a bare file
+    /// name with no path separators or `..` components, since it is joined
+    /// directly onto the resolved installation directory.
+    pub name: String,
+}
+
+impl Artifact {
+    /// Describe a binary to be installed into [`dirs::bindir()`].
+    pub fn binary(src: impl Into<PathBuf>, name: impl Into<String>) -> Self {
+        Self {
+            src: src.into(),
+            dir: dirs::bindir().to_path_buf(),
+            name: name.into(),
+        }
+    }
+
+    /// Describe a man page to be installed into [`dirs::mandir()`].
+    pub fn man_page(src: impl Into<PathBuf>, name: impl Into<String>) -> Self {
+        Self {
+            src: src.into(),
+            dir: dirs::mandir().to_path_buf(),
+            name: name.into(),
+        }
+    }
+
+    /// Describe a config template to be installed into
+    /// [`dirs::sysconfdir()`].
+    pub fn config(src: impl Into<PathBuf>, name: impl Into<String>) -> Self {
+        Self {
+            src: src.into(),
+            dir: dirs::sysconfdir().to_path_buf(),
+            name: name.into(),
+        }
+    }
+}
+
+/// Prepend the `DESTDIR` environment variable, if set, to `dir`.
+///
+/// This follows the standard GNU convention: the final install location of
+/// a directory is `DESTDIR + dir`, with `DESTDIR` left unset (or empty) for
+/// a direct, non-staged install.
+pub fn staged(dir: &Path) -> PathBuf {
+    match std::env::var_os(DESTDIR) {
+        Some(destdir) if !destdir.is_empty() => {
+            let mut path = PathBuf::from(destdir);
+            path.push(dir.strip_prefix("/").unwrap_or(dir));
+            path
+        }
+        _ => dir.to_path_buf(),
+    }
+}
+
+/// Stage `artifact` under its resolved, `DESTDIR`-prefixed directory,
+/// creating any missing parent directories and copying (never moving) the
+/// source file into place.
+///
+/// Returns an [`io::Error`] of kind [`io::ErrorKind::InvalidInput`] if
+/// `artifact.name` is not a bare file name, so a traversal or absolute
+/// component can never carry the write outside the resolved directory.
+pub fn install(artifact: &Artifact) -> io::Result<PathBuf> {
+    let mut components = Path::new(&artifact.name).components();
+    match (components.next(), components.next()) {
+        (Some(Component::Normal(n)), None) if n == artifact.name.as_str() => {}
+        _ => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "artifact name must be a bare file name, got {:?}",
+                    artifact.name
+                ),
+            ))
+        }
+    }
+
+    let dest_dir = staged(&artifact.dir);
+    fs::create_dir_all(&dest_dir)?;
+
+    let dest = dest_dir.join(&artifact.name);
+    fs::copy(&artifact.src, &dest)?;
+
+    Ok(dest)
+}
+
+/// Stage every artifact in `artifacts`, returning the destination path of
+/// each once installed.
+pub fn install_all<'a, I>(artifacts: I) -> io::Result<Vec<PathBuf>>
+where
+    I: IntoIterator<Item = &'a Artifact>,
+{
+    artifacts.into_iter().map(install).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// An `Artifact` pointing at paths that do not exist, for exercising
+    /// `install()`'s name check before it ever touches the file system.
+    fn artifact_named(name: &str) -> Artifact {
+        Artifact {
+            src: PathBuf::from("/nonexistent/src"),
+            dir: PathBuf::from("/nonexistent/dir"),
+            name: String::from(name),
+        }
+    }
+
+    fn assert_rejected(name: &str) {
+        let err = install(&artifact_named(name)).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn rejects_empty_name() {
+        assert_rejected("");
+    }
+
+    #[test]
+    fn rejects_current_dir() {
+        assert_rejected(".");
+    }
+
+    #[test]
+    fn rejects_parent_dir() {
+        assert_rejected("..");
+    }
+
+    #[test]
+    fn rejects_traversal() {
+        assert_rejected("../evil");
+    }
+
+    #[test]
+    fn rejects_absolute_path() {
+        assert_rejected("/etc/passwd");
+    }
+
+    #[test]
+    fn rejects_nested_path() {
+        assert_rejected("a/b");
+    }
+
+    #[test]
+    fn accepts_bare_name() {
+        let tmp = std::env::temp_dir().join(format!(
+            "gitrs-install-test-{}-{}",
+            std::process::id(),
+            "accepts_bare_name"
+        ));
+        let src = tmp.join("src-file");
+        fs::create_dir_all(&tmp).unwrap();
+        fs::write(&src, b"hello").unwrap();
+
+        let artifact = Artifact {
+            src,
+            dir: tmp.join("dest"),
+            name: String::from("hello.txt"),
+        };
+
+        let dest = install(&artifact).unwrap();
+        assert_eq!(dest, tmp.join("dest").join("hello.txt"));
+        assert_eq!(fs::read(&dest).unwrap(), b"hello");
+
+        fs::remove_dir_all(&tmp).ok();
+    }
+}
@@ -27,5 +27,13 @@
 //! currently unstable, use [git2](https://crates.io/crates/git2) in the
 //! meanwhile.
 
+pub mod dirs;
+pub mod install;
+
 /// Replicate the version of the package as provided by Cargo.
 pub const VERSION_STR: &'static str = env!("CARGO_PKG_VERSION");
+
+/// Name of the DEFLATE/crypto backend selected by `build.rs`, either
+/// `"system-zlib"` when linked against the system library or `"bundled"`
+/// otherwise.
+pub const COMPRESSION_BACKEND: &'static str = env!("COMPRESSION_BACKEND");